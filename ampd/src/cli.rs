@@ -19,8 +19,12 @@ use service_registry::msg::ExecuteMsg;
 use crate::broadcaster;
 use crate::broadcaster::{accounts::account, Broadcaster, Config as BroadcastConfig};
 use crate::config::Config;
+use crate::fees::{self, ConfirmationTarget};
 use crate::handlers;
+use crate::keystore::{default_keystore_dir, Keystore, LocalKey, LocalSigner};
 use crate::report::Error;
+use crate::retry;
+use crate::simulate;
 use crate::state::StateUpdater;
 use crate::tofnd::grpc::{MultisigClient, SharableEcdsaClient};
 use crate::types::PublicKey;
@@ -32,17 +36,166 @@ const PREFIX: &str = "axelar";
 
 #[derive(Args, Debug)]
 pub struct BondWorkerArgs {
-    pub service_registry: String,
     pub service_name: String,
     pub amount: u128,
     pub denom: String,
+
+    /// Service registry contract address; defaults to the selected network's registry
+    #[arg(long)]
+    pub service_registry: Option<String>,
 }
 
 #[derive(Args, Debug)]
 pub struct DeclareChainSupportArgs {
-    pub service_registry: String,
     pub service_name: String,
     pub chains: Vec<String>,
+
+    /// Service registry contract address; defaults to the selected network's registry
+    #[arg(long)]
+    pub service_registry: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct KeysAddArgs {
+    /// Name to store the new key under
+    pub name: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct KeysRestoreArgs {
+    /// Name to store the restored key under
+    pub name: String,
+}
+
+/// Generates a fresh BIP39 mnemonic, derives a secp256k1 key from it, and stores it
+/// encrypted in the local keystore. The mnemonic is printed once so the operator can
+/// back it up; ampd itself never persists it.
+pub async fn keys_add(params: KeysAddArgs) {
+    let password = rpassword::prompt_password("Enter a password to encrypt the new key: ")
+        .expect("failed to read password");
+
+    let keystore = Keystore::new(default_keystore_dir()).expect("failed to open keystore");
+    let (mnemonic, signer) = keystore
+        .add(&params.name, &password)
+        .expect("failed to generate key");
+
+    println!("Mnemonic (write this down, it will not be shown again):\n{}", mnemonic.phrase());
+    println!(
+        "Key '{}' added, address is {}",
+        signer.name(),
+        signer.account_id(PREFIX).expect("failed to convert to account identifier")
+    );
+}
+
+/// Imports an existing BIP39 mnemonic and stores the derived key encrypted in the
+/// local keystore.
+pub async fn keys_restore(params: KeysRestoreArgs) {
+    let phrase = rpassword::prompt_password("Enter the mnemonic to restore: ")
+        .expect("failed to read mnemonic");
+    let password = rpassword::prompt_password("Enter a password to encrypt the key: ")
+        .expect("failed to read password");
+
+    let keystore = Keystore::new(default_keystore_dir()).expect("failed to open keystore");
+    let signer = keystore
+        .restore(&params.name, &password, phrase.trim())
+        .expect("failed to restore key");
+
+    println!(
+        "Key '{}' restored, address is {}",
+        signer.name(),
+        signer.account_id(PREFIX).expect("failed to convert to account identifier")
+    );
+}
+
+/// Lists the names of every key currently held in the local keystore.
+pub async fn keys_list() {
+    let keystore = Keystore::new(default_keystore_dir()).expect("failed to open keystore");
+    let names = keystore.list().expect("failed to read keystore");
+
+    if names.is_empty() {
+        println!("No local keys found");
+    } else {
+        names.iter().for_each(|name| println!("{name}"));
+    }
+}
+
+/// Either backend the broadcaster can sign with: a tofnd-hosted MPC key, or a key held
+/// in the local encrypted keystore. Lets every CLI command work without a running
+/// tofnd instance when a `--key-name` is given.
+#[derive(Clone)]
+enum SignerBackend {
+    Tofnd(SharableEcdsaClient),
+    Local(LocalSigner),
+}
+
+impl broadcaster::Signer for SignerBackend {
+    fn pub_key(&self) -> PublicKey {
+        match self {
+            SignerBackend::Tofnd(signer) => signer.pub_key(),
+            SignerBackend::Local(signer) => signer.pub_key(),
+        }
+    }
+
+    fn sign(&self, msg: &[u8]) -> error_stack::Result<Vec<u8>, Error> {
+        match self {
+            SignerBackend::Tofnd(signer) => signer.sign(msg),
+            SignerBackend::Local(signer) => signer.sign(msg),
+        }
+    }
+}
+
+impl SignerBackend {
+    /// Derives the public key to register with the multisig prover contract at
+    /// `multisig_address`: tofnd generates a fresh key scoped to that contract via
+    /// `keygen`, while a local signer has only the one worker key and reuses it.
+    async fn multisig_pub_key(&self, multisig_address: &str) -> MultisigPublicKey {
+        let pub_key = match self {
+            SignerBackend::Local(signer) => signer.pub_key(),
+            SignerBackend::Tofnd(signer) => signer.keygen(multisig_address).await.unwrap(),
+        };
+
+        MultisigPublicKey::try_from((KeyType::Ecdsa, pub_key.to_bytes().into())).unwrap()
+    }
+}
+
+/// Resolves the signer to broadcast with: loads the named local key when `local_key`
+/// is set, otherwise connects to tofnd as before. Returns the signer, the key
+/// identifier to pass to the broadcaster, and the worker's public key.
+async fn resolve_signer(
+    tofnd_config: crate::config::TofndConfig,
+    state_path: PathBuf,
+    local_key: Option<LocalKey>,
+) -> (SignerBackend, String, PublicKey) {
+    match local_key {
+        Some(LocalKey { name, password }) => {
+            let keystore = Keystore::new(default_keystore_dir()).expect("failed to open keystore");
+            let signer = keystore
+                .load(&name, &password)
+                .expect("failed to load key from keystore");
+            let pub_key = signer.pub_key();
+
+            (SignerBackend::Local(signer), name, pub_key)
+        }
+        None => {
+            let multisig_client =
+                MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
+                    .await
+                    .change_context(Error::Connection)
+                    .unwrap();
+
+            let ecdsa_client = SharableEcdsaClient::new(multisig_client);
+
+            let pub_key = pub_key(
+                state_path,
+                tofnd_config.key_uid.as_str(),
+                ecdsa_client.clone(),
+            )
+            .await
+            .unwrap();
+
+            (SignerBackend::Tofnd(ecdsa_client), tofnd_config.key_uid, pub_key)
+        }
+    }
 }
 
 pub async fn bond_worker(
@@ -51,6 +204,9 @@ pub async fn bond_worker(
     service_registry: AccountId,
     service_name: String,
     coin: Coin,
+    simulate: bool,
+    confirmation_target: ConfirmationTarget,
+    local_key: Option<LocalKey>,
 ) {
     let Config {
         tm_grpc,
@@ -59,20 +215,7 @@ pub async fn bond_worker(
         ..
     } = config;
 
-    let multisig_client = MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
-        .await
-        .change_context(Error::Connection)
-        .unwrap();
-
-    let ecdsa_client = SharableEcdsaClient::new(multisig_client);
-
-    let pub_key = pub_key(
-        state_path,
-        tofnd_config.key_uid.as_str(),
-        ecdsa_client.clone(),
-    )
-    .await
-    .unwrap();
+    let (signer, key_uid, pub_key) = resolve_signer(tofnd_config, state_path, local_key).await;
 
     let msg = serde_json::to_vec(&ExecuteMsg::BondWorker { service_name })
         .expect("bond worker msg should serialize");
@@ -89,10 +232,12 @@ pub async fn bond_worker(
     broadcast_execute_contract(
         tm_grpc,
         broadcast,
-        tofnd_config.key_uid,
+        key_uid,
         tx,
         pub_key,
-        ecdsa_client,
+        signer,
+        simulate,
+        confirmation_target,
     )
     .await
 }
@@ -103,6 +248,9 @@ pub async fn declare_chain_support(
     service_registry: AccountId,
     service_name: String,
     chains: Vec<String>,
+    simulate: bool,
+    confirmation_target: ConfirmationTarget,
+    local_key: Option<LocalKey>,
 ) {
     let Config {
         tm_grpc,
@@ -111,20 +259,7 @@ pub async fn declare_chain_support(
         ..
     } = config;
 
-    let multisig_client = MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
-        .await
-        .change_context(Error::Connection)
-        .unwrap();
-
-    let ecdsa_client = SharableEcdsaClient::new(multisig_client);
-
-    let pub_key = pub_key(
-        state_path,
-        tofnd_config.key_uid.as_str(),
-        ecdsa_client.clone(),
-    )
-    .await
-    .unwrap();
+    let (signer, key_uid, pub_key) = resolve_signer(tofnd_config, state_path, local_key).await;
 
     let msg = serde_json::to_vec(&ExecuteMsg::DeclareChainSupport {
         service_name,
@@ -144,15 +279,23 @@ pub async fn declare_chain_support(
     broadcast_execute_contract(
         tm_grpc,
         broadcast,
-        tofnd_config.key_uid,
+        key_uid,
         tx,
         pub_key,
-        ecdsa_client,
+        signer,
+        simulate,
+        confirmation_target,
     )
     .await
 }
 
-pub async fn register_public_key(config: Config, state_path: PathBuf) {
+pub async fn register_public_key(
+    config: Config,
+    state_path: PathBuf,
+    simulate: bool,
+    confirmation_target: ConfirmationTarget,
+    local_key: Option<LocalKey>,
+) {
     let Config {
         tm_grpc,
         broadcast,
@@ -161,21 +304,6 @@ pub async fn register_public_key(config: Config, state_path: PathBuf) {
         ..
     } = config;
 
-    let multisig_client = MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
-        .await
-        .change_context(Error::Connection)
-        .unwrap();
-
-    let ecdsa_client = SharableEcdsaClient::new(multisig_client);
-
-    let pub_key = pub_key(
-        state_path,
-        tofnd_config.key_uid.as_str(),
-        ecdsa_client.clone(),
-    )
-    .await
-    .unwrap();
-
     // get multisig contract address
     let multisig_address = handlers
         .iter()
@@ -189,13 +317,11 @@ pub async fn register_public_key(config: Config, state_path: PathBuf) {
         .next()
         .expect("No multisig signer found in handlers");
 
-    // get tofnd pub key
-    let multisig_pub_key = ecdsa_client
-        .keygen(multisig_address.to_string().as_str())
-        .await
-        .unwrap();
-    let multisig_pub_key =
-        MultisigPublicKey::try_from((KeyType::Ecdsa, multisig_pub_key.to_bytes().into())).unwrap();
+    let (signer, key_uid, pub_key) = resolve_signer(tofnd_config, state_path, local_key).await;
+    let multisig_pub_key = signer
+        .multisig_pub_key(multisig_address.to_string().as_str())
+        .await;
+
     let msg = serde_json::to_vec(&MultisigExecuteMsg::RegisterPublicKey {
         public_key: multisig_pub_key,
     })
@@ -213,34 +339,20 @@ pub async fn register_public_key(config: Config, state_path: PathBuf) {
     broadcast_execute_contract(
         tm_grpc,
         broadcast,
-        tofnd_config.key_uid,
+        key_uid,
         tx,
         pub_key,
-        ecdsa_client,
+        signer,
+        simulate,
+        confirmation_target,
     )
     .await
 }
 
-pub async fn worker_address(config: Config, state_path: PathBuf) {
-    let Config {
-        tofnd_config,
-        ..
-    } = config;
-
-    let multisig_client = MultisigClient::connect(tofnd_config.party_uid, tofnd_config.url)
-        .await
-        .change_context(Error::Connection)
-        .unwrap();
-
-    let ecdsa_client = SharableEcdsaClient::new(multisig_client);
+pub async fn worker_address(config: Config, state_path: PathBuf, local_key: Option<LocalKey>) {
+    let Config { tofnd_config, .. } = config;
 
-    let pub_key = pub_key(
-        state_path,
-        tofnd_config.key_uid.as_str(),
-        ecdsa_client.clone(),
-    )
-    .await
-    .unwrap();
+    let (_, _, pub_key) = resolve_signer(tofnd_config, state_path, local_key).await;
 
     println!(
         "Worker address is {}",
@@ -272,14 +384,18 @@ async fn pub_key(
     }
 }
 
-async fn broadcast_execute_contract(
+async fn broadcast_execute_contract<S>(
     tm_grpc: Url,
     broadcast: BroadcastConfig,
     key_uid: String,
     tx: MsgExecuteContract,
     pub_key: PublicKey,
-    ecdsa_client: SharableEcdsaClient,
-) {
+    signer: S,
+    simulate: bool,
+    confirmation_target: ConfirmationTarget,
+) where
+    S: broadcaster::Signer + Clone,
+{
     let query_client = QueryClient::connect(tm_grpc.to_string())
         .await
         .change_context(Error::Connection)
@@ -298,20 +414,87 @@ async fn broadcast_execute_contract(
         .await
         .change_context(Error::Connection)
         .unwrap();
+    let msg = tx.into_any().unwrap();
+
+    if simulate {
+        let response = simulate::simulate(service_client, vec![msg], &worker, pub_key, account.sequence)
+            .await
+            .unwrap();
+
+        println!("gas_used: {}", response.gas_info.map(|info| info.gas_used).unwrap_or_default());
+        response.result.into_iter().for_each(|result| {
+            result.events.iter().for_each(|event| println!("event: {event:?}"));
+        });
 
-    let mut broadcaster = broadcaster::BroadcastClientBuilder::default()
-        .client(service_client)
-        .signer(ecdsa_client.clone())
+        return;
+    }
+
+    let mut builder = broadcaster::BroadcastClientBuilder::default();
+    builder
+        .client(service_client.clone())
+        .signer(signer.clone())
         .acc_number(account.account_number)
         .acc_sequence(account.sequence)
         .pub_key((key_uid, pub_key))
-        .config(broadcast.clone())
-        .build()
-        .change_context(Error::Broadcaster)
-        .unwrap();
+        .config(broadcast.clone());
 
-    broadcaster
-        .broadcast(vec![tx.into_any().unwrap()])
+    // always simulate to size the gas limit off the real tx, falling back to the SDK's
+    // default adjustment factor when the operator hasn't configured one explicitly
+    let response = simulate::simulate(service_client, vec![msg.clone()], &worker, pub_key, account.sequence)
         .await
         .unwrap();
+    let gas_used = response.gas_info.map(|info| info.gas_used).unwrap_or_default();
+
+    let gas_adjustment = broadcast.gas_adjustment.unwrap_or(simulate::DEFAULT_GAS_ADJUSTMENT);
+    builder.gas_limit(simulate::adjusted_gas_limit(gas_used, gas_adjustment));
+
+    if let Some(fee_denom) = broadcast.fee_denom.clone() {
+        let fee_estimator = fees::FeeEstimator::new(
+            fee_denom,
+            broadcast.floor_gas_price,
+            broadcast.min_gas_price,
+        );
+        let base_gas_price = fee_estimator.base_gas_price(&tm_grpc).await;
+        builder.fee_amount(fee_estimator.estimate(gas_used, base_gas_price, confirmation_target));
+    }
+
+    let mut broadcaster = builder.build().change_context(Error::Broadcaster).unwrap();
+    let max_retries = broadcast.max_retries;
+
+    for attempt in 0..=max_retries {
+        // `Broadcaster::broadcast` only errors here on a transport-level failure (the
+        // node was unreachable, the request timed out, ...); a tx that made it to the
+        // chain comes back as `Ok(TxResponse)` with its ABCI `code` set, zero or not, so
+        // `retry::classify` below is what's responsible for turning a non-zero code into
+        // a `Failed`/`SequenceMismatch` outcome, not this line.
+        let response = broadcaster
+            .broadcast(vec![msg.clone()])
+            .await
+            .change_context(Error::Broadcaster)
+            .unwrap();
+
+        match retry::classify(Some(response.code), &response.raw_log, response) {
+            retry::BroadcastOutcome::Success(_) => return,
+            retry::BroadcastOutcome::SequenceMismatch(expected) if attempt < max_retries => {
+                let query_client = QueryClient::connect(tm_grpc.to_string())
+                    .await
+                    .change_context(Error::Connection)
+                    .unwrap();
+                let account = account(query_client, &worker)
+                    .await
+                    .change_context(Error::Broadcaster)
+                    .unwrap();
+                let acc_sequence = account.sequence.max(expected);
+
+                builder.acc_sequence(acc_sequence);
+                broadcaster = builder.build().change_context(Error::Broadcaster).unwrap();
+            }
+            retry::BroadcastOutcome::SequenceMismatch(_) => {
+                retry::exhausted(max_retries, max_retries).unwrap();
+            }
+            retry::BroadcastOutcome::Failed(response) => {
+                retry::failed(response.code, &response.raw_log).unwrap();
+            }
+        }
+    }
 }
@@ -0,0 +1,156 @@
+use clap::ValueEnum;
+use cosmos_sdk_proto::cosmos::base::node::v1beta1::service_client::ServiceClient as NodeConfigClient;
+use cosmos_sdk_proto::cosmos::base::node::v1beta1::ConfigRequest;
+use cosmrs::Coin;
+use error_stack::ResultExt;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::report::Error;
+use crate::url::Url;
+
+type Result<T> = error_stack::Result<T, Error>;
+
+/// How urgently a transaction should confirm, traded off against how much the
+/// operator is willing to pay over the chain's floor gas price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationTarget {
+    /// Willing to wait several blocks; pay close to the floor price
+    Background,
+    /// Default: confirm within a block or two
+    Normal,
+    /// Confirm as fast as possible regardless of cost
+    HighPriority,
+}
+
+impl Default for ConfirmationTarget {
+    fn default() -> Self {
+        ConfirmationTarget::Normal
+    }
+}
+
+impl ConfirmationTarget {
+    /// Multiplier applied to the base gas price to arrive at the price actually paid.
+    fn multiplier(self) -> f64 {
+        match self {
+            ConfirmationTarget::Background => 1.0,
+            ConfirmationTarget::Normal => 1.2,
+            ConfirmationTarget::HighPriority => 2.0,
+        }
+    }
+}
+
+/// Derives a fee from a gas estimate, a confirmation target, and a base gas price,
+/// clamped so the gas price paid never falls below the configured `min_gas_price`.
+#[derive(Debug, Clone)]
+pub struct FeeEstimator {
+    denom: String,
+    /// Gas price floor to fall back on when the node's `minimum_gas_price` can't be
+    /// queried, or doesn't quote a price in `denom`.
+    floor_gas_price: f64,
+    /// Absolute minimum gas price a broadcast may use, regardless of target.
+    min_gas_price: f64,
+}
+
+impl FeeEstimator {
+    pub fn new(denom: String, floor_gas_price: f64, min_gas_price: f64) -> Self {
+        FeeEstimator {
+            denom,
+            floor_gas_price,
+            min_gas_price,
+        }
+    }
+
+    /// Queries the node's `minimum_gas_price` over the `cosmos.base.node.v1beta1`
+    /// service and returns the quoted price in [`Self::denom`], falling back to
+    /// `floor_gas_price` if the node is unreachable or quotes a different denom.
+    pub async fn base_gas_price(&self, tm_grpc: &Url) -> f64 {
+        match self.query_minimum_gas_price(tm_grpc).await {
+            Ok(Some(price)) => price,
+            Ok(None) => {
+                warn!(
+                    denom = self.denom,
+                    "node did not quote a minimum gas price in the fee denom, using configured floor"
+                );
+                self.floor_gas_price
+            }
+            Err(err) => {
+                warn!(err = err.to_string(), "failed to query node gas price, using configured floor");
+                self.floor_gas_price
+            }
+        }
+    }
+
+    async fn query_minimum_gas_price(&self, tm_grpc: &Url) -> Result<Option<f64>> {
+        let mut client = NodeConfigClient::connect(tm_grpc.to_string())
+            .await
+            .change_context(Error::Connection)?;
+
+        let response = client
+            .config(ConfigRequest {})
+            .await
+            .change_context(Error::Connection)?
+            .into_inner();
+
+        Ok(parse_gas_price(&response.minimum_gas_price, &self.denom))
+    }
+
+    /// Computes `fee = gas_used * base_price * target_multiplier`, clamped to
+    /// `min_gas_price` so the fee never drops below the chain's fee-rate floor.
+    pub fn estimate(&self, gas_used: u64, base_gas_price: f64, target: ConfirmationTarget) -> Coin {
+        let gas_price = (base_gas_price * target.multiplier()).max(self.min_gas_price);
+        let amount = (gas_used as f64 * gas_price).ceil() as u128;
+
+        Coin {
+            denom: self.denom.parse().expect("fee denom should be valid"),
+            amount,
+        }
+    }
+}
+
+/// Parses a `DecCoins`-style minimum gas price string (e.g. `"0.025uaxl"`, possibly a
+/// comma-separated list of such entries) and returns the amount quoted in `denom`.
+fn parse_gas_price(raw: &str, denom: &str) -> Option<f64> {
+    raw.split(',').find_map(|entry| {
+        let entry = entry.trim();
+        let split_at = entry.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (amount, entry_denom) = entry.split_at(split_at);
+
+        (entry_denom == denom).then(|| amount.parse().ok()).flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_gas_price_finds_matching_denom() {
+        assert_eq!(parse_gas_price("0.025uaxl", "uaxl"), Some(0.025));
+        assert_eq!(parse_gas_price("0.01uatom,0.025uaxl", "uaxl"), Some(0.025));
+        assert_eq!(parse_gas_price("0.01uatom", "uaxl"), None);
+        assert_eq!(parse_gas_price("", "uaxl"), None);
+    }
+
+    #[test]
+    fn estimate_applies_target_multiplier() {
+        let estimator = FeeEstimator::new("uaxl".to_string(), 0.007, 0.005);
+
+        let background = estimator.estimate(100_000, 0.007, ConfirmationTarget::Background);
+        let high_priority = estimator.estimate(100_000, 0.007, ConfirmationTarget::HighPriority);
+
+        assert_eq!(background.amount, 700);
+        assert_eq!(high_priority.amount, 1400);
+        assert!(high_priority.amount > background.amount);
+    }
+
+    #[test]
+    fn estimate_never_drops_below_min_gas_price() {
+        let estimator = FeeEstimator::new("uaxl".to_string(), 0.0001, 0.01);
+
+        let fee = estimator.estimate(100_000, 0.0001, ConfirmationTarget::Background);
+
+        assert_eq!(fee.amount, 1000);
+    }
+}
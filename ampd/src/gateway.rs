@@ -0,0 +1,190 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::cli;
+use crate::config::Config;
+
+/// Runtime snapshot the admin gateway reports back over `/status`. `handlers` is fixed
+/// for the process lifetime and is filled in once by `gateway::spawn` from the
+/// configured handler list; `account_number`, `acc_sequence`,
+/// `last_processed_block_height`, `tm_grpc_connected` and `tofnd_connected` change as
+/// blocks are processed and connections are (re)established, so only `run`'s handler
+/// loop is in a position to keep those current. The gateway only ever reads this.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Status {
+    pub account_number: Option<u64>,
+    pub acc_sequence: Option<u64>,
+    pub handlers: Vec<String>,
+    pub last_processed_block_height: Option<u64>,
+    pub tm_grpc_connected: bool,
+    pub tofnd_connected: bool,
+}
+
+pub type SharedStatus = Arc<RwLock<Status>>;
+
+#[derive(Clone)]
+struct GatewayState {
+    status: SharedStatus,
+    config: Config,
+    state_path: PathBuf,
+    auth_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct HealthResponse {
+    ok: bool,
+}
+
+/// Spawns the admin gateway's HTTP server on `bind_addr` and returns immediately; the
+/// server runs on its own task until the process exits. `auth_token` must be presented
+/// as a bearer token on every `/control/*` request; when it is `None`, every
+/// `/control/*` request is refused.
+pub fn spawn(
+    bind_addr: SocketAddr,
+    status: SharedStatus,
+    config: Config,
+    state_path: PathBuf,
+    auth_token: Option<String>,
+) -> JoinHandle<()> {
+    let handler_names = config.handlers.iter().map(|handler| format!("{handler:?}")).collect();
+
+    let state = GatewayState {
+        status,
+        config,
+        state_path,
+        auth_token,
+    };
+
+    let app = Router::new()
+        .route("/health", get(health))
+        .route("/status", get(status_handler))
+        .route("/control/register-public-key", post(register_public_key))
+        .route("/control/declare-chain-support", post(declare_chain_support))
+        .with_state(state.clone());
+
+    tokio::spawn(async move {
+        state.status.write().await.handlers = handler_names;
+
+        info!(addr = bind_addr.to_string(), "starting admin gateway");
+
+        let listener = match tokio::net::TcpListener::bind(bind_addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                tracing::error!(err = err.to_string(), "failed to bind admin gateway");
+                return;
+            }
+        };
+
+        if let Err(err) = axum::serve(listener, app).await {
+            tracing::error!(err = err.to_string(), "admin gateway exited");
+        }
+    })
+}
+
+async fn health() -> Json<HealthResponse> {
+    Json(HealthResponse { ok: true })
+}
+
+async fn status_handler(State(state): State<GatewayState>) -> Json<Status> {
+    Json(state.status.read().await.clone())
+}
+
+/// Checks the bearer token on a `/control/*` request. Unlike `/health` and `/status`,
+/// the control routes trigger real signed broadcasts, so a missing `auth_token`
+/// refuses every request rather than defaulting open — operators who want the control
+/// routes reachable must configure a token explicitly.
+fn authorized(state: &GatewayState, headers: &HeaderMap) -> bool {
+    let Some(expected) = &state.auth_token else {
+        return false;
+    };
+
+    headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| token == expected)
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DeclareChainSupportRequest {
+    service_registry: String,
+    service_name: String,
+    chains: Vec<String>,
+}
+
+async fn register_public_key(
+    State(state): State<GatewayState>,
+    headers: HeaderMap,
+) -> StatusCode {
+    if !authorized(&state, &headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let config = state.config.clone();
+    let state_path = state.state_path.clone();
+
+    // cli::register_public_key is expect/unwrap-heavy by CLI convention; run it on its
+    // own task so a panic fails this request with a 500 instead of taking the gateway down
+    match tokio::spawn(async move {
+        cli::register_public_key(config, state_path, false, Default::default(), None).await;
+    })
+    .await
+    {
+        Ok(()) => StatusCode::ACCEPTED,
+        Err(err) => {
+            tracing::error!(err = err.to_string(), "register-public-key request failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+async fn declare_chain_support(
+    State(state): State<GatewayState>,
+    headers: HeaderMap,
+    Json(params): Json<DeclareChainSupportRequest>,
+) -> StatusCode {
+    if !authorized(&state, &headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let Ok(service_registry) = params.service_registry.parse() else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let config = state.config.clone();
+    let state_path = state.state_path.clone();
+
+    // cli::declare_chain_support is expect/unwrap-heavy by CLI convention; run it on its
+    // own task so a panic fails this request with a 500 instead of taking the gateway down
+    match tokio::spawn(async move {
+        cli::declare_chain_support(
+            config,
+            state_path,
+            service_registry,
+            params.service_name,
+            params.chains,
+            false,
+            Default::default(),
+            None,
+        )
+        .await;
+    })
+    .await
+    {
+        Ok(()) => StatusCode::ACCEPTED,
+        Err(err) => {
+            tracing::error!(err = err.to_string(), "declare-chain-support request failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use bip32::{DerivationPath, Mnemonic, XPrv};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::AccountId;
+use error_stack::{Report, ResultExt};
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{Signature, SigningKey as EcdsaSigningKey};
+use rand_core::{OsRng, RngCore};
+use scrypt::password_hash::Salt;
+use serde::{Deserialize, Serialize};
+
+use crate::broadcaster;
+use crate::report::Error;
+use crate::types::PublicKey;
+
+type Result<T> = error_stack::Result<T, Error>;
+
+/// BIP32 derivation path used for every locally managed key, matching the path the
+/// Cosmos SDK's `keyring` uses for secp256k1 accounts.
+const DERIVATION_PATH: &str = "m/44'/118'/0'/0/0";
+
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// A secp256k1 signer whose key material lives in an encrypted file on disk instead of
+/// behind a tofnd connection. Exposes the same `account_id`/signing surface
+/// [`crate::tofnd::grpc::SharableEcdsaClient`] does, so callers can treat the two
+/// interchangeably.
+#[derive(Clone)]
+pub struct LocalSigner {
+    name: String,
+    signing_key: SigningKey,
+}
+
+impl LocalSigner {
+    pub fn pub_key(&self) -> PublicKey {
+        PublicKey::from(self.signing_key.public_key())
+    }
+
+    pub fn account_id(&self, prefix: &str) -> Result<AccountId> {
+        self.signing_key
+            .public_key()
+            .account_id(prefix)
+            .change_context(Error::Keystore)
+    }
+
+    /// Signs `msg`, which the broadcaster always hands over as a pre-computed 32-byte
+    /// sign-doc digest — the same contract tofnd's `SharableEcdsaClient::sign` relies on
+    /// ([`crate::broadcaster::Signer`]). `cosmrs::crypto::secp256k1::SigningKey::sign`
+    /// hashes its input with SHA-256 before signing, which would double-hash an
+    /// already-hashed digest and produce a signature the chain rejects, so we sign the
+    /// prehash directly against the underlying k256 key instead.
+    pub fn sign(&self, msg: &[u8]) -> Result<Vec<u8>> {
+        let signing_key =
+            EcdsaSigningKey::from_slice(&self.signing_key.to_bytes()).change_context(Error::Keystore)?;
+        let signature: Signature = signing_key.sign_prehash(msg).change_context(Error::Keystore)?;
+
+        Ok(signature.to_bytes().to_vec())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Lets [`LocalSigner`] stand in for [`crate::tofnd::grpc::SharableEcdsaClient`]
+/// anywhere the broadcaster needs to sign a transaction.
+impl broadcaster::Signer for LocalSigner {
+    fn pub_key(&self) -> PublicKey {
+        self.pub_key()
+    }
+
+    fn sign(&self, msg: &[u8]) -> error_stack::Result<Vec<u8>, Error> {
+        self.sign(msg)
+    }
+}
+
+/// On-disk representation of an encrypted key: the scrypt salt used to derive the
+/// encryption key from the user's password, the AEAD nonce, and the ciphertext of the
+/// raw secp256k1 signing key bytes. The scrypt parameters are the fixed `SCRYPT_*`
+/// constants and are not stored in the file.
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedKey {
+    salt: String,
+    nonce: [u8; 12],
+    ciphertext: Vec<u8>,
+}
+
+/// Manages encrypted key files under a keystore directory (`~/.ampd/keys/` by default).
+pub struct Keystore {
+    dir: PathBuf,
+}
+
+impl Keystore {
+    pub fn new(dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&dir).change_context(Error::Keystore)?;
+        Ok(Keystore { dir })
+    }
+
+    /// Generates a new BIP39 mnemonic, derives the default account key from it, and
+    /// encrypts it under `name` with `password`. Returns the mnemonic so the operator
+    /// can back it up; it is not stored anywhere.
+    pub fn add(&self, name: &str, password: &str) -> Result<(Mnemonic, LocalSigner)> {
+        let mnemonic = Mnemonic::random(OsRng, Default::default());
+        let signer = self.restore(name, password, mnemonic.phrase())?;
+        Ok((mnemonic, signer))
+    }
+
+    /// Imports an existing mnemonic, derives the default account key, and encrypts it
+    /// under `name` with `password`.
+    pub fn restore(&self, name: &str, password: &str, phrase: &str) -> Result<LocalSigner> {
+        let mnemonic =
+            Mnemonic::new(phrase, Default::default()).change_context(Error::Keystore)?;
+        let seed = mnemonic.to_seed("");
+        let path: DerivationPath = DERIVATION_PATH.parse().change_context(Error::Keystore)?;
+        let xprv = XPrv::derive_from_path(seed, &path).change_context(Error::Keystore)?;
+        let signing_key = SigningKey::from_slice(&xprv.private_key().to_bytes())
+            .change_context(Error::Keystore)?;
+
+        self.save(name, password, &signing_key)?;
+
+        Ok(LocalSigner {
+            name: name.to_string(),
+            signing_key,
+        })
+    }
+
+    pub fn load(&self, name: &str, password: &str) -> Result<LocalSigner> {
+        let path = self.key_path(name);
+        let bytes = fs::read(&path).change_context(Error::Keystore)?;
+        let encrypted: EncryptedKey =
+            serde_json::from_slice(&bytes).change_context(Error::Keystore)?;
+
+        let salt = Salt::from_b64(&encrypted.salt).change_context(Error::Keystore)?;
+        let cipher = derive_cipher(password, &salt)?;
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&encrypted.nonce), encrypted.ciphertext.as_ref())
+            .map_err(|_| Report::new(Error::Keystore))?;
+
+        let signing_key = SigningKey::from_slice(&plaintext).change_context(Error::Keystore)?;
+
+        Ok(LocalSigner {
+            name: name.to_string(),
+            signing_key,
+        })
+    }
+
+    pub fn list(&self) -> Result<Vec<String>> {
+        let mut names = fs::read_dir(&self.dir)
+            .change_context(Error::Keystore)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.path().file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .collect::<Vec<_>>();
+        names.sort();
+        Ok(names)
+    }
+
+    fn save(&self, name: &str, password: &str, signing_key: &SigningKey) -> Result<()> {
+        let salt = Salt::generate(&mut OsRng);
+        let cipher = derive_cipher(password, &salt)?;
+
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), signing_key.to_bytes().as_slice())
+            .map_err(|_| Report::new(Error::Keystore))?;
+
+        let encrypted = EncryptedKey {
+            salt: salt.as_str().to_string(),
+            nonce: nonce_bytes,
+            ciphertext,
+        };
+
+        let bytes = serde_json::to_vec_pretty(&encrypted).change_context(Error::Keystore)?;
+        fs::write(self.key_path(name), bytes).change_context(Error::Keystore)
+    }
+
+    fn key_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+fn derive_cipher(password: &str, salt: &Salt) -> Result<Aes256Gcm> {
+    let mut key_bytes = [0u8; 32];
+    scrypt::scrypt(
+        password.as_bytes(),
+        salt.as_str().as_bytes(),
+        &scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, 32).change_context(Error::Keystore)?,
+        &mut key_bytes,
+    )
+    .change_context(Error::Keystore)?;
+
+    Aes256Gcm::new_from_slice(&key_bytes).change_context(Error::Keystore)
+}
+
+/// Identifies a key in the local keystore and the password needed to decrypt it, as
+/// selected by the `--key-name` CLI flag.
+#[derive(Clone)]
+pub struct LocalKey {
+    pub name: String,
+    pub password: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_then_load_round_trips_the_same_key() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let keystore = Keystore::new(dir.path().to_path_buf()).unwrap();
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        let restored = keystore.restore("default", "correct horse", phrase).unwrap();
+        let loaded = keystore.load("default", "correct horse").unwrap();
+
+        assert_eq!(restored.pub_key().to_bytes(), loaded.pub_key().to_bytes());
+    }
+
+    #[test]
+    fn load_with_wrong_password_fails() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let keystore = Keystore::new(dir.path().to_path_buf()).unwrap();
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        keystore.restore("default", "correct horse", phrase).unwrap();
+
+        assert!(keystore.load("default", "wrong password").is_err());
+    }
+
+    #[test]
+    fn list_returns_every_stored_key_name() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let keystore = Keystore::new(dir.path().to_path_buf()).unwrap();
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        keystore.restore("alice", "password", phrase).unwrap();
+        keystore.restore("bob", "password", phrase).unwrap();
+
+        assert_eq!(keystore.list().unwrap(), vec!["alice".to_string(), "bob".to_string()]);
+    }
+}
+
+pub fn default_keystore_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| Path::new(".").to_path_buf())
+        .join(".ampd")
+        .join("keys")
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs::canonicalize;
 use std::path::{Path, PathBuf};
@@ -8,12 +9,17 @@ use clap::{command, Parser, Subcommand, ValueEnum};
 use config::ConfigError;
 use cosmrs::{AccountId, Coin};
 use error_stack::{Report, ResultExt};
+use serde::Deserialize;
 use tracing::{error, info};
 use valuable::Valuable;
 
 use ampd::cli;
-use ampd::cli::{BondWorkerArgs, DeclareChainSupportArgs};
+use ampd::cli::{BondWorkerArgs, DeclareChainSupportArgs, KeysAddArgs, KeysRestoreArgs};
 use ampd::config::Config;
+use ampd::fees::ConfirmationTarget;
+use ampd::gateway;
+use ampd::keystore::LocalKey;
+use ampd::networks::{self, NetworkPreset};
 use ampd::report::Error;
 use ampd::report::LoggableError;
 use ampd::run;
@@ -35,6 +41,26 @@ struct Args {
     #[arg(short, long, value_enum, default_value_t = Output::Json)]
     pub output: Output,
 
+    /// Simulate the transaction instead of broadcasting it, printing the estimated gas
+    /// and resulting events
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// How urgently the broadcast transaction should confirm, trading off fee for speed
+    #[arg(long, value_enum, default_value_t = ConfirmationTarget::Normal)]
+    pub confirmation_target: ConfirmationTarget,
+
+    /// Select a named network's preset endpoints and contract addresses: one of the
+    /// built-ins (`devnet`, `testnet`, `mainnet`) or a custom name defined under a
+    /// `[networks.<name>]` section in the config file. Explicit config values and
+    /// flags still take precedence over the preset.
+    #[arg(long)]
+    pub network: Option<String>,
+
+    /// Sign with the named key from the local keystore instead of connecting to tofnd
+    #[arg(long)]
+    pub key_name: Option<String>,
+
     #[clap(subcommand)]
     pub cmd: Option<SubCommand>,
 }
@@ -56,6 +82,19 @@ enum SubCommand {
     /// Register worker public key to the multisig signer
     RegisterPublicKey,
     WorkerAddress,
+    /// Manage locally held mnemonic-backed signing keys
+    #[clap(subcommand)]
+    Keys(KeysCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum KeysCommand {
+    /// Generate a new mnemonic-backed key and store it encrypted in the local keystore
+    Add(KeysAddArgs),
+    /// Import an existing mnemonic into the local keystore
+    Restore(KeysRestoreArgs),
+    /// List the keys held in the local keystore
+    List,
 }
 
 #[tokio::main]
@@ -88,15 +127,30 @@ async fn main() -> ExitCode {
         }
         Some(SubCommand::RegisterPublicKey) => register_public_key(&args).await,
         Some(SubCommand::WorkerAddress) => worker_address(&args).await,
+        Some(SubCommand::Keys(cmd)) => keys(cmd).await,
     }
 }
 
+async fn keys(cmd: &KeysCommand) -> ExitCode {
+    match cmd {
+        KeysCommand::Add(params) => cli::keys_add(params.clone()).await,
+        KeysCommand::Restore(params) => cli::keys_restore(params.clone()).await,
+        KeysCommand::List => cli::keys_list().await,
+    }
+
+    ExitCode::SUCCESS
+}
+
 async fn bond_worker(args: &Args, params: &BondWorkerArgs) -> ExitCode {
     info!("registering worker");
 
-    let cfg = init_config(&args.config);
+    let cfg = init_config(&args.config, &args.network);
+    let custom_networks = load_custom_networks(&args.config);
     let coin = Coin::new(params.amount, params.denom.as_str()).unwrap();
-    let service_registry = params.service_registry.parse::<AccountId>().unwrap();
+    let service_registry =
+        resolve_service_registry(&params.service_registry, &args.network, &custom_networks)
+            .parse::<AccountId>()
+            .expect("service registry address must be a valid bech32 account id");
 
     cli::bond_worker(
         cfg,
@@ -104,6 +158,9 @@ async fn bond_worker(args: &Args, params: &BondWorkerArgs) -> ExitCode {
         service_registry,
         params.service_name.clone(),
         coin,
+        args.dry_run,
+        args.confirmation_target,
+        resolve_local_key(&args.key_name),
     )
     .await;
 
@@ -113,8 +170,12 @@ async fn bond_worker(args: &Args, params: &BondWorkerArgs) -> ExitCode {
 async fn declare_chain_support(args: &Args, params: &DeclareChainSupportArgs) -> ExitCode {
     info!("declaring chain support");
 
-    let cfg = init_config(&args.config);
-    let service_registry = params.service_registry.parse::<AccountId>().unwrap();
+    let cfg = init_config(&args.config, &args.network);
+    let custom_networks = load_custom_networks(&args.config);
+    let service_registry =
+        resolve_service_registry(&params.service_registry, &args.network, &custom_networks)
+            .parse::<AccountId>()
+            .expect("service registry address must be a valid bech32 account id");
 
     cli::declare_chain_support(
         cfg,
@@ -122,6 +183,9 @@ async fn declare_chain_support(args: &Args, params: &DeclareChainSupportArgs) ->
         service_registry,
         params.service_name.clone(),
         params.chains.clone(),
+        args.dry_run,
+        args.confirmation_target,
+        resolve_local_key(&args.key_name),
     )
     .await;
 
@@ -131,8 +195,15 @@ async fn declare_chain_support(args: &Args, params: &DeclareChainSupportArgs) ->
 async fn register_public_key(args: &Args) -> ExitCode {
     info!("registering public key to multisig signer contract");
 
-    let cfg = init_config(&args.config);
-    cli::register_public_key(cfg, args.state.clone()).await;
+    let cfg = init_config(&args.config, &args.network);
+    cli::register_public_key(
+        cfg,
+        args.state.clone(),
+        args.dry_run,
+        args.confirmation_target,
+        resolve_local_key(&args.key_name),
+    )
+    .await;
 
     ExitCode::SUCCESS
 }
@@ -140,12 +211,23 @@ async fn register_public_key(args: &Args) -> ExitCode {
 async fn worker_address(args: &Args) -> ExitCode {
     info!("querying worker address");
 
-    let cfg = init_config(&args.config);
-    cli::worker_address(cfg, args.state.clone()).await;
+    let cfg = init_config(&args.config, &args.network);
+    cli::worker_address(cfg, args.state.clone(), resolve_local_key(&args.key_name)).await;
 
     ExitCode::SUCCESS
 }
 
+/// Prompts for the keystore password when `--key-name` was given, producing the
+/// `LocalKey` selector the CLI functions use to sign without tofnd.
+fn resolve_local_key(key_name: &Option<String>) -> Option<LocalKey> {
+    key_name.clone().map(|name| {
+        let password = rpassword::prompt_password(format!("Enter password for key '{name}': "))
+            .expect("failed to read password");
+
+        LocalKey { name, password }
+    })
+}
+
 
 fn set_up_logger(output: &Output) {
     match output {
@@ -159,21 +241,71 @@ fn set_up_logger(output: &Output) {
 }
 
 async fn run_daemon(args: &Args) -> Result<(), Report<Error>> {
-    let cfg = init_config(&args.config);
+    let cfg = init_config(&args.config, &args.network);
     let state_path = expand_home_dir(args.state.as_path());
+    let status = gateway::SharedStatus::default();
+
+    if let Some(bind_addr) = cfg.admin_gateway_bind_addr {
+        gateway::spawn(
+            bind_addr,
+            status.clone(),
+            cfg.clone(),
+            state_path.clone(),
+            cfg.admin_gateway_auth_token.clone(),
+        );
+    }
 
-    run(cfg, state_path).await
+    run(cfg, state_path, status).await
 }
 
-fn init_config(config_paths: &[PathBuf]) -> Config {
+/// Resolves the service registry address to use: an explicit CLI value always wins,
+/// otherwise falls back to the selected network's preset registry.
+fn resolve_service_registry(
+    service_registry: &Option<String>,
+    network: &Option<String>,
+    custom_networks: &HashMap<String, NetworkPreset>,
+) -> String {
+    service_registry.clone().unwrap_or_else(|| {
+        let name = network
+            .as_deref()
+            .expect("service_registry must be set explicitly when --network is not given");
+
+        networks::resolve(name, custom_networks)
+            .unwrap_or_else(|| panic!("unknown network '{name}'"))
+            .service_registry
+    })
+}
+
+fn init_config(config_paths: &[PathBuf], network: &Option<String>) -> Config {
     let files = find_config_files(config_paths);
+    let custom_networks = load_custom_networks(config_paths);
 
-    parse_config(files)
+    parse_config(files, network, &custom_networks)
         .change_context(Error::LoadConfig)
         .tap_err(|report| error!(err = LoggableError::from(report).as_value(), "{report}"))
         .unwrap_or(Config::default())
 }
 
+/// Deserializes the `[networks.<name>]` section of the config files, if present, so a
+/// custom network can be selected with `--network <name>` the same way a built-in one
+/// can. Config files are parsed a second time here (without the network preset source,
+/// which depends on this result) since the section has to be known before the preset
+/// it contributes can be merged in.
+fn load_custom_networks(config_paths: &[PathBuf]) -> HashMap<String, NetworkPreset> {
+    #[derive(Debug, Default, Deserialize)]
+    struct NetworksSection {
+        #[serde(default)]
+        networks: HashMap<String, NetworkPreset>,
+    }
+
+    cfg::builder()
+        .add_source(find_config_files(config_paths))
+        .build()
+        .and_then(|built| built.try_deserialize::<NetworksSection>())
+        .map(|section| section.networks)
+        .unwrap_or_default()
+}
+
 fn find_config_files(config: &[PathBuf]) -> Vec<File<FileSourceFile, FileFormat>> {
     let files = config
         .iter()
@@ -192,10 +324,36 @@ fn find_config_files(config: &[PathBuf]) -> Vec<File<FileSourceFile, FileFormat>
     files
 }
 
+fn network_preset_source(preset: &NetworkPreset) -> File<config::FileSourceString, FileFormat> {
+    let toml = format!(
+        "tm_grpc = \"{}\"\nchain_id = \"{}\"\n\n[broadcast]\nchain_id = \"{}\"\n",
+        preset.tm_grpc, preset.chain_id, preset.chain_id,
+    );
+
+    File::from_str(toml.as_str(), FileFormat::Toml)
+}
+
 fn parse_config(
     files: Vec<File<FileSourceFile, FileFormat>>,
+    network: &Option<String>,
+    custom_networks: &HashMap<String, NetworkPreset>,
 ) -> error_stack::Result<Config, ConfigError> {
-    cfg::builder()
+    let mut builder = cfg::builder();
+
+    // the network preset is the lowest-priority source: explicit config files and
+    // environment variables loaded after it still override its values
+    if let Some(name) = network {
+        let preset = networks::resolve(name, custom_networks).ok_or_else(|| {
+            ConfigError::Message(format!(
+                "unknown network '{name}': expected a built-in (devnet, testnet, mainnet) \
+                 or a [networks.{name}] section in the config"
+            ))
+        })?;
+
+        builder = builder.add_source(network_preset_source(&preset));
+    }
+
+    builder
         .add_source(files)
         .add_source(Environment::with_prefix(clap::crate_name!()))
         .build()?
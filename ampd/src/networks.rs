@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// The preset bundle of endpoints and deployed contract addresses for a named network,
+/// either one of the well-known built-ins below or a custom entry an operator defines
+/// under `[networks.<name>]` in their config.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkPreset {
+    pub tm_grpc: String,
+    pub chain_id: String,
+    pub service_registry: String,
+}
+
+/// Looks up one of the networks bundled with ampd by name.
+fn built_in(name: &str) -> Option<NetworkPreset> {
+    let preset = match name {
+        "devnet" => NetworkPreset {
+            tm_grpc: "http://devnet-grpc.axelar.dev:9090".to_string(),
+            chain_id: "devnet-amplifier".to_string(),
+            service_registry: "axelar1qzr9xgf2tvdw0s3jn54khce6mua7lqpzljf57l".to_string(),
+        },
+        "testnet" => NetworkPreset {
+            tm_grpc: "http://testnet-grpc.axelar.dev:9090".to_string(),
+            chain_id: "axelar-testnet-lisbon-3".to_string(),
+            service_registry: "axelar1x8gf2tvdw0s3jn54khce6mua7lqpzry9tnqqa8".to_string(),
+        },
+        "mainnet" => NetworkPreset {
+            tm_grpc: "http://mainnet-grpc.axelar.dev:9090".to_string(),
+            chain_id: "axelar-dojo-1".to_string(),
+            service_registry: "axelar1gf2tvdw0s3jn54khce6mua7lqpzry9x8ej5ss6".to_string(),
+        },
+        _ => return None,
+    };
+
+    Some(preset)
+}
+
+/// Resolves `name` to a preset: the built-ins (`devnet`, `testnet`, `mainnet`) take
+/// priority, falling back to `custom` (populated from the config's `[networks.<name>]`
+/// tables) so operators can plug in their own named networks the same way.
+pub fn resolve(name: &str, custom: &HashMap<String, NetworkPreset>) -> Option<NetworkPreset> {
+    built_in(name).or_else(|| custom.get(name).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_prefers_built_in_over_custom_of_the_same_name() {
+        let mut custom = HashMap::new();
+        custom.insert(
+            "devnet".to_string(),
+            NetworkPreset {
+                tm_grpc: "http://overridden:9090".to_string(),
+                chain_id: "overridden".to_string(),
+                service_registry: "axelar1overridden".to_string(),
+            },
+        );
+
+        let preset = resolve("devnet", &custom).unwrap();
+
+        assert_eq!(preset.chain_id, "devnet-amplifier");
+    }
+
+    #[test]
+    fn resolve_falls_back_to_custom_network() {
+        let mut custom = HashMap::new();
+        custom.insert(
+            "local".to_string(),
+            NetworkPreset {
+                tm_grpc: "http://localhost:9090".to_string(),
+                chain_id: "local-1".to_string(),
+                service_registry: "axelar1local".to_string(),
+            },
+        );
+
+        let preset = resolve("local", &custom).unwrap();
+
+        assert_eq!(preset.chain_id, "local-1");
+    }
+
+    #[test]
+    fn resolve_returns_none_for_unknown_network() {
+        assert!(resolve("nonexistent", &HashMap::new()).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use error_stack::{Report, ResultExt};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tracing::warn;
+
+use crate::report::Error;
+
+/// Cosmos SDK ABCI error code for `sdkerrors.ErrWrongSequence` (account sequence
+/// mismatch).
+const ACCOUNT_SEQUENCE_MISMATCH_CODE: u32 = 32;
+
+static SEQUENCE_MISMATCH_LOG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"account sequence mismatch, expected (\d+)").expect("valid regex"));
+
+/// Broadcast outcome relevant to sequence-mismatch recovery: the broadcast succeeded,
+/// failed with the account sequence the chain expects instead, or failed for some
+/// other reason that retrying won't fix.
+pub enum BroadcastOutcome<T> {
+    Success(T),
+    SequenceMismatch(u64),
+    Failed(T),
+}
+
+/// Classifies a broadcast result: ABCI code `0` (or no code at all) is a success, code
+/// 32 (`ErrWrongSequence`) is inspected for the sequence number the chain actually
+/// expects in the error log (a code 32 whose log does not name it is `Failed`), and any
+/// other non-zero code is a real failure that must not be swallowed.
+pub fn classify<T>(code: Option<u32>, raw_log: &str, result: T) -> BroadcastOutcome<T> {
+    match code {
+        None | Some(0) => BroadcastOutcome::Success(result),
+        Some(ACCOUNT_SEQUENCE_MISMATCH_CODE) => SEQUENCE_MISMATCH_LOG
+            .captures(raw_log)
+            .and_then(|captures| captures.get(1))
+            .and_then(|m| m.as_str().parse::<u64>().ok())
+            .map(|expected| {
+                warn!(expected, "account sequence mismatch, retrying with updated sequence");
+                BroadcastOutcome::SequenceMismatch(expected)
+            })
+            .unwrap_or(BroadcastOutcome::Failed(result)),
+        Some(_) => BroadcastOutcome::Failed(result),
+    }
+}
+
+pub fn exhausted(attempts: u64, max_retries: u64) -> error_stack::Result<(), Error> {
+    Err(Report::new(Error::Broadcaster))
+        .attach_printable(format!(
+            "account sequence mismatch persisted after {attempts} retries (max {max_retries})"
+        ))
+        .change_context(Error::Broadcaster)
+}
+
+pub fn failed(code: u32, raw_log: &str) -> error_stack::Result<(), Error> {
+    Err(Report::new(Error::Broadcaster))
+        .attach_printable(format!("broadcast failed with code {code}: {raw_log}"))
+        .change_context(Error::Broadcaster)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_treats_code_zero_and_no_code_as_success() {
+        assert!(matches!(classify(Some(0), "", ()), BroadcastOutcome::Success(())));
+        assert!(matches!(classify(None, "", ()), BroadcastOutcome::Success(())));
+    }
+
+    #[test]
+    fn classify_extracts_expected_sequence_from_mismatch_log() {
+        let raw_log = "account sequence mismatch, expected 42, got 41: incorrect account sequence";
+
+        assert!(matches!(
+            classify(Some(ACCOUNT_SEQUENCE_MISMATCH_CODE), raw_log, ()),
+            BroadcastOutcome::SequenceMismatch(42)
+        ));
+    }
+
+    #[test]
+    fn classify_treats_code_32_without_a_matching_log_as_failed() {
+        assert!(matches!(
+            classify(Some(ACCOUNT_SEQUENCE_MISMATCH_CODE), "some unrelated error", ()),
+            BroadcastOutcome::Failed(())
+        ));
+    }
+
+    #[test]
+    fn classify_treats_other_nonzero_codes_as_failed() {
+        assert!(matches!(classify(Some(5), "insufficient funds", ()), BroadcastOutcome::Failed(())));
+    }
+}
@@ -0,0 +1,85 @@
+use cosmos_sdk_proto::cosmos::tx::v1beta1::service_client::ServiceClient;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{SimulateRequest, SimulateResponse, TxRaw};
+use cosmrs::tx::{Body, Fee, SignerInfo};
+use cosmrs::{AccountId, Any};
+use error_stack::ResultExt;
+use prost::Message;
+use tonic::transport::Channel;
+use tracing::info;
+
+use crate::report::Error;
+use crate::types::PublicKey;
+
+type Result<T> = error_stack::Result<T, Error>;
+
+/// Default multiplier applied to a simulated `gas_used` to arrive at the gas limit
+/// used for the real broadcast, matching the Cosmos SDK CLI's `--gas-adjustment`
+/// default.
+pub const DEFAULT_GAS_ADJUSTMENT: f64 = 1.3;
+
+/// Submits `msgs` to the node's `Simulate` RPC using a throwaway signature (the node
+/// does not verify signatures during simulation) and returns the simulated response.
+pub async fn simulate(
+    mut service_client: ServiceClient<Channel>,
+    msgs: Vec<Any>,
+    signer: &AccountId,
+    pub_key: PublicKey,
+    acc_sequence: u64,
+) -> Result<SimulateResponse> {
+    let body = Body::new(msgs, "", 0u32);
+    let auth_info = SignerInfo::single_direct(Some(pub_key.into()), acc_sequence)
+        .auth_info(Fee::from_amount_and_gas(cosmrs::Coin {
+            denom: "uaxl".parse().change_context(Error::Broadcaster)?,
+            amount: 0,
+        }, 0u64));
+
+    // the node does not check the signature during simulation, so any fixed-size
+    // throwaway value of the right length is accepted
+    let throwaway_signature = vec![0u8; 64];
+    let tx_raw = TxRaw {
+        body_bytes: body.into_bytes().change_context(Error::Broadcaster)?,
+        auth_info_bytes: auth_info.into_bytes().change_context(Error::Broadcaster)?,
+        signatures: vec![throwaway_signature],
+    };
+
+    let request = SimulateRequest {
+        tx: None,
+        tx_bytes: tx_raw.encode_to_vec(),
+    };
+
+    let response = service_client
+        .simulate(request)
+        .await
+        .change_context(Error::Broadcaster)?
+        .into_inner();
+
+    info!(
+        gas_used = response.gas_info.as_ref().map(|info| info.gas_used),
+        signer = signer.to_string(),
+        "simulated transaction"
+    );
+
+    Ok(response)
+}
+
+/// Scales a simulated `gas_used` by `adjustment` to get a safety-margined gas limit.
+pub fn adjusted_gas_limit(gas_used: u64, adjustment: f64) -> u64 {
+    ((gas_used as f64) * adjustment).ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjusted_gas_limit_scales_and_rounds_up() {
+        assert_eq!(adjusted_gas_limit(100_000, DEFAULT_GAS_ADJUSTMENT), 130_000);
+        assert_eq!(adjusted_gas_limit(100_001, 1.0), 100_001);
+        assert_eq!(adjusted_gas_limit(3, 1.5), 5);
+    }
+
+    #[test]
+    fn adjusted_gas_limit_is_a_no_op_at_1x() {
+        assert_eq!(adjusted_gas_limit(42, 1.0), 42);
+    }
+}